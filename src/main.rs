@@ -7,8 +7,9 @@ use std::io::{self, Read, Write};
 
 /* ========== Enums ========== */
 
-/// Size of the program memory in bytes.
-const PROGRAM_MEMORY_SIZE: usize = 8;
+/// Default size of the program memory in bytes, matching the conventional
+/// brainfuck tape length.
+const DEFAULT_PROGRAM_MEMORY_SIZE: usize = 30000;
 
 /* ========== Enums ========== */
 
@@ -41,8 +42,70 @@ enum Command {
     JumpBackward = b']',
 }
 
+/// Policy for what happens to the current cell when `,` reads input and
+/// encounters EOF. Different brainfuck programs assume different
+/// conventions, so this is configurable rather than hardcoded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+enum EofMode {
+    /// Write a 0 byte into the cell.
+    #[default]
+    Zero,
+    /// Write 255 (-1 as a wrapped `u8`) into the cell.
+    NegativeOne,
+    /// Leave the cell's current value untouched.
+    Unchanged,
+}
+
+/// A toggleable execution feature that changes the semantics of pointer
+/// or value operations, letting the interpreter emulate dialects of
+/// brainfuck that define wraparound differently than this one's default.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Feature {
+    /// Wrap `IncrementPointer`/`DecrementPointer` around the ends of the
+    /// tape instead of growing it / erroring. Off by default, since
+    /// growing the tape is this interpreter's default pointer behavior.
+    WrappingPointer,
+    /// Wrap `IncrementValue`/`DecrementValue` around 0/255 instead of
+    /// saturating at the boundary. This is the value analogue of
+    /// `WrappingPointer`, and is on by default since wrapping cell
+    /// arithmetic is this interpreter's existing default behavior.
+    ReverseCounter,
+}
+
+/// The default feature set: wrapping cell arithmetic on, wrapping pointer
+/// off, matching this interpreter's behavior before features existed.
+fn default_features() -> Vec<Feature> {
+    vec![Feature::ReverseCounter]
+}
+
 /* ========== Structs ========== */
 
+/// Result of running a program under the debugger, describing why
+/// execution stopped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ExecutionStatus {
+    /// A single instruction was executed and nothing halted execution. This
+    /// is also returned when that instruction was the last one; the next
+    /// step then reports `EndOfProgram`.
+    Running,
+    /// The instruction pointer ran off the end of the code.
+    EndOfProgram,
+    /// Execution stopped at a breakpoint.
+    HitBreakpoint,
+    /// Execution stopped because a watched cell's value changed.
+    HitWatchpoint,
+}
+
+/// A memory cell being watched for changes, along with the value it held
+/// the last time it was checked.
+#[derive(Debug, Clone, Copy)]
+struct Watchpoint {
+    /// Index of the watched cell.
+    cell: usize,
+    /// Value of the cell as of the last check.
+    last_value: u8,
+}
+
 /// Contains the current state of the program.
 #[derive(Debug, Clone)]
 struct ProgramState {
@@ -52,24 +115,37 @@ struct ProgramState {
     /// Pointer to the instruction currently being executed.
     pub instruct_ptr: usize,
 
-    /// Current state of the program memory.
-    pub memory: [u8; PROGRAM_MEMORY_SIZE],
+    /// Current state of the program memory. Grows as the data pointer is
+    /// advanced past its current end.
+    pub memory: Vec<u8>,
 }
 
 impl ProgramState {
-    /// Create a new ProgramState object.
-    pub fn new() -> Self {
-        let memory = [0u8; PROGRAM_MEMORY_SIZE];
+    /// Create a new ProgramState object with a tape of `tape_size` cells.
+    pub fn new(tape_size: usize) -> Self {
         Self {
             data_ptr: 0,
             instruct_ptr: 0,
-            memory,
+            memory: vec![0u8; tape_size],
         }
     }
 
     /// Given a program command, return a new state equivalent to the current
     /// state with the command applied to it.
-    pub fn step(&self, code: &[Command]) -> Result<ProgramState> {
+    ///
+    /// `jump_table` must be the table produced by [`build_jump_table`] for
+    /// `code`; it maps each bracket's index to the index of its match so
+    /// that jumps are O(1) instead of rescanning the code. `eof_mode`
+    /// controls what `,` writes into the current cell on EOF. `features`
+    /// is the set of enabled [`Feature`]s controlling pointer/value
+    /// wraparound semantics.
+    pub fn step(
+        &self,
+        code: &[Command],
+        jump_table: &[usize],
+        eof_mode: EofMode,
+        features: &[Feature],
+    ) -> Result<ProgramState> {
         let mut new_state = self.clone();
         let command = code[new_state.instruct_ptr];
 
@@ -82,15 +158,43 @@ impl ProgramState {
         match command {
             Command::IncrementPointer => {
                 new_state.data_ptr += 1;
+                if new_state.data_ptr >= new_state.memory.len() {
+                    if features.contains(&Feature::WrappingPointer) {
+                        new_state.data_ptr = 0;
+                    } else {
+                        // Grow the tape on demand instead of panicking on overflow.
+                        new_state.memory.push(0);
+                    }
+                }
             }
             Command::DecrementPointer => {
-                new_state.data_ptr -= 1;
+                new_state.data_ptr = match new_state.data_ptr.checked_sub(1) {
+                    Some(ptr) => ptr,
+                    None if features.contains(&Feature::WrappingPointer) => {
+                        new_state.memory.len() - 1
+                    }
+                    None => {
+                        return Err(eyre::eyre!(
+                            "data pointer decremented below the start of the tape"
+                        ))
+                    }
+                };
             }
             Command::IncrementValue => {
-                new_state.memory[new_state.data_ptr] += 1;
+                let value = new_state.memory[new_state.data_ptr];
+                new_state.memory[new_state.data_ptr] = if features.contains(&Feature::ReverseCounter) {
+                    value.wrapping_add(1)
+                } else {
+                    value.saturating_add(1)
+                };
             }
             Command::DecrementValue => {
-                new_state.memory[new_state.data_ptr] -= 1;
+                let value = new_state.memory[new_state.data_ptr];
+                new_state.memory[new_state.data_ptr] = if features.contains(&Feature::ReverseCounter) {
+                    value.wrapping_sub(1)
+                } else {
+                    value.saturating_sub(1)
+                };
             }
             Command::OutputValue => {
                 // Print the raw byte directly to stdout.
@@ -99,60 +203,27 @@ impl ProgramState {
                 let _ = io::stdout().flush();
             }
             Command::InputValue => {
-                // TODO: Not sure of the best way to accept user input.
-                // let mut input = String::new();
                 let mut buf = [0];
-                let _ = io::stdin().read(&mut buf);
-                new_state.memory[new_state.data_ptr] = buf[0];
-            }
-            Command::JumpForward => {
-                let value = new_state.get_value();
-                if value == 0 {
-                    // Find matching end bracket.
-                    let mut instruct_ptr = new_state.instruct_ptr;
-                    let end = code.len();
-                    let mut bracket_counter = 1;
-                    while instruct_ptr < end && bracket_counter > 0 {
-                        instruct_ptr += 1;
-                        match code[instruct_ptr] {
-                            Command::JumpForward => {
-                                bracket_counter += 1;
-                            }
-                            Command::JumpBackward => {
-                                bracket_counter -= 1;
-                            }
-                            _ => {}
-                        }
+                let bytes_read = io::stdin().read(&mut buf)?;
+                new_state.memory[new_state.data_ptr] = if bytes_read == 0 {
+                    match eof_mode {
+                        EofMode::Zero => 0,
+                        EofMode::NegativeOne => 255,
+                        EofMode::Unchanged => new_state.get_value(),
                     }
-                    // New instruction is after the end bracket, but this is
-                    // handled at the very end of the step function.
-                    // println!("New instruct_ptr: {}", instruct_ptr);
-                    new_state.instruct_ptr = instruct_ptr;
-                }
+                } else {
+                    buf[0]
+                };
             }
-            Command::JumpBackward => {
-                let value = new_state.get_value();
-                if value != 0 {
-                    // Find the matching start bracket.
-                    let mut instruct_ptr = new_state.instruct_ptr;
-                    let mut bracket_counter = 1;
-                    while bracket_counter > 0 {
-                        instruct_ptr -= 1;
-                        match code[instruct_ptr] {
-                            Command::JumpForward => {
-                                bracket_counter -= 1;
-                            }
-                            Command::JumpBackward => {
-                                bracket_counter += 1;
-                            }
-                            _ => {}
-                        }
-                    }
-                    // New instruction is after the start bracket, but this is
-                    // handled at the very end of the step function.
-                    // println!("New instruct_ptr: {}", instruct_ptr);
-                    new_state.instruct_ptr = instruct_ptr;
-                }
+            Command::JumpForward if new_state.get_value() == 0 => {
+                // Jump straight to the matching end bracket using the
+                // precomputed table instead of rescanning the code.
+                new_state.instruct_ptr = jump_table[new_state.instruct_ptr];
+            }
+            Command::JumpBackward if new_state.get_value() != 0 => {
+                // Jump straight to the matching start bracket using the
+                // precomputed table instead of rescanning the code.
+                new_state.instruct_ptr = jump_table[new_state.instruct_ptr];
             }
             _ => {
                 // For any other command, do nothing.
@@ -173,76 +244,645 @@ impl ProgramState {
 
 impl Default for ProgramState {
     fn default() -> Self {
-        Self::new()
+        Self::new(DEFAULT_PROGRAM_MEMORY_SIZE)
     }
 }
 
 /// The program runner.
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Clone)]
 struct Program {
     /// The current state of the program.
     state: ProgramState,
+
+    /// The parsed commands making up the program.
+    code: Vec<Command>,
+
+    /// Maps the index of each `[`/`]` to the index of its matching bracket,
+    /// as produced by [`build_jump_table`].
+    jump_table: Vec<usize>,
+
+    /// Initial size of the tape, used when resetting the program state.
+    tape_size: usize,
+
+    /// Instruction pointers that halt execution when reached.
+    breakpoints: Vec<usize>,
+
+    /// Memory cells being watched for changes.
+    watchpoints: Vec<Watchpoint>,
+
+    /// Number of instructions executed so far.
+    step_count: usize,
+
+    /// Set after a breakpoint has been reported at the current instruction
+    /// pointer, so the following `step_one` call actually executes that
+    /// instruction instead of reporting the same breakpoint again.
+    breakpoint_acknowledged: bool,
+
+    /// What `,` writes into the current cell when input hits EOF.
+    eof_mode: EofMode,
+
+    /// Enabled execution features controlling pointer/value wraparound.
+    features: Vec<Feature>,
+}
+
+impl Default for Program {
+    /// An empty program with the conventional 30000-cell tape, no EOF
+    /// override, and no opt-in features. `Program::new` is only fallible on
+    /// unbalanced brackets, so this can never fail for empty code.
+    fn default() -> Self {
+        Self::new(
+            Vec::new(),
+            DEFAULT_PROGRAM_MEMORY_SIZE,
+            EofMode::default(),
+            default_features(),
+        )
+        .expect("empty program is always valid")
+    }
 }
 
 impl Program {
-    /// Create a new Program object.
-    pub fn new() -> Self {
-        Self::default()
+    /// Parse and validate a program, building its bracket jump table up
+    /// front so that `run` never has to rescan the code to find a match.
+    /// `tape_size` is the number of cells the memory tape starts with; it
+    /// grows automatically as the data pointer advances past the end.
+    /// `eof_mode` controls what `,` does when input is exhausted.
+    /// `features` selects which wraparound dialects are enabled.
+    pub fn new(
+        code: Vec<Command>,
+        tape_size: usize,
+        eof_mode: EofMode,
+        features: Vec<Feature>,
+    ) -> Result<Self> {
+        let jump_table = build_jump_table(&code)?;
+        Ok(Self {
+            state: ProgramState::new(tape_size),
+            code,
+            jump_table,
+            tape_size,
+            breakpoints: Vec::new(),
+            watchpoints: Vec::new(),
+            step_count: 0,
+            breakpoint_acknowledged: false,
+            eof_mode,
+            features,
+        })
     }
 
-    /// Reset the program state back to default.
-    #[allow(dead_code)]
+    /// Reset the program back to default: state, code, and jump table are
+    /// all cleared, so the next `extend_code` starts a fresh program rather
+    /// than appending to (and re-running) everything typed so far.
     pub fn reset(&mut self) {
-        self.state = ProgramState::new();
+        self.state = ProgramState::new(self.tape_size);
+        self.code.clear();
+        self.jump_table.clear();
+        self.step_count = 0;
+        self.breakpoint_acknowledged = false;
     }
 
-    /// Run a set of commands to completion.
-    pub fn run(&mut self, code: &[Command]) -> Result<()> {
-        while self.state.instruct_ptr < code.len() {
-            self.state = self.state.step(code)?;
+    /// Run the program to completion.
+    ///
+    /// This is only meaningful for a `Program` with no breakpoints or
+    /// watchpoints set; `run` has no way to pause and report a hit the way
+    /// [`Program::run_until`] does, so it errors out instead of silently
+    /// treating a breakpoint/watchpoint hit as ordinary progress.
+    pub fn run(&mut self) -> Result<()> {
+        while self.state.instruct_ptr < self.code.len() {
+            match self.step_one()? {
+                ExecutionStatus::Running | ExecutionStatus::EndOfProgram => {}
+                status @ (ExecutionStatus::HitBreakpoint | ExecutionStatus::HitWatchpoint) => {
+                    return Err(eyre::eyre!(
+                        "run() cannot report a {:?}; use run_until() instead",
+                        status
+                    ));
+                }
+            }
         }
         Ok(())
     }
+
+    /// Append more commands to the end of the program, rebuilding the jump
+    /// table, without disturbing the current execution state. Used by the
+    /// REPL to grow the program one line at a time.
+    ///
+    /// If the combined code fails to validate (e.g. an unmatched bracket),
+    /// `self.code` is left unchanged so the caller can keep prompting for
+    /// more input instead of the program being left in a half-extended
+    /// state.
+    pub fn extend_code(&mut self, commands: Vec<Command>) -> Result<()> {
+        let previous_len = self.code.len();
+        self.code.extend(commands);
+        match build_jump_table(&self.code) {
+            Ok(jump_table) => {
+                self.jump_table = jump_table;
+                Ok(())
+            }
+            Err(e) => {
+                self.code.truncate(previous_len);
+                Err(e)
+            }
+        }
+    }
+
+    /// Halt execution the next time instruction pointer `ip` is reached.
+    pub fn add_breakpoint(&mut self, ip: usize) {
+        self.breakpoints.push(ip);
+    }
+
+    /// Halt execution the next time the value in `cell` changes.
+    pub fn add_watchpoint(&mut self, cell: usize) {
+        let last_value = self.state.memory.get(cell).copied().unwrap_or(0);
+        self.watchpoints.push(Watchpoint { cell, last_value });
+    }
+
+    /// Execute a single instruction, incrementing the step counter and
+    /// checking breakpoints and watchpoints.
+    ///
+    /// A breakpoint is checked *before* the instruction at that pointer
+    /// runs, so `break 0` halts before anything executes, matching a
+    /// breakpoint set anywhere else in the program. Once reported, the
+    /// breakpoint is acknowledged so the next call actually executes the
+    /// instruction instead of halting on it again.
+    pub fn step_one(&mut self) -> Result<ExecutionStatus> {
+        if self.state.instruct_ptr >= self.code.len() {
+            return Ok(ExecutionStatus::EndOfProgram);
+        }
+
+        if self.breakpoints.contains(&self.state.instruct_ptr) && !self.breakpoint_acknowledged {
+            self.breakpoint_acknowledged = true;
+            return Ok(ExecutionStatus::HitBreakpoint);
+        }
+        self.breakpoint_acknowledged = false;
+
+        self.state = self
+            .state
+            .step(&self.code, &self.jump_table, self.eof_mode, &self.features)?;
+        self.step_count += 1;
+
+        for watch in &mut self.watchpoints {
+            let current = self.state.memory.get(watch.cell).copied().unwrap_or(0);
+            if current != watch.last_value {
+                watch.last_value = current;
+                return Ok(ExecutionStatus::HitWatchpoint);
+            }
+        }
+
+        if self.breakpoints.contains(&self.state.instruct_ptr) {
+            self.breakpoint_acknowledged = true;
+            return Ok(ExecutionStatus::HitBreakpoint);
+        }
+
+        Ok(ExecutionStatus::Running)
+    }
+
+    /// Run until the program ends, a breakpoint is hit, or a watchpoint
+    /// fires.
+    pub fn run_until(&mut self) -> Result<ExecutionStatus> {
+        loop {
+            let status = self.step_one()?;
+            if status != ExecutionStatus::Running {
+                return Ok(status);
+            }
+        }
+    }
 }
 
 /* ========== Functions ========== */
 
-/// Check brackets in the input code to make sure they are valid.
-#[allow(dead_code)]
-fn validate_brackets(_code: &[u8]) -> Result<()> {
-    // TODO
+/// Build a table mapping each `[`/`]` instruction to the index of its
+/// matching bracket, so that jumps can be resolved in O(1) at run time.
+///
+/// Returns an error if the code contains unbalanced brackets.
+fn build_jump_table(code: &[Command]) -> Result<Vec<usize>> {
+    let mut jump_table = vec![0usize; code.len()];
+    let mut open_brackets = Vec::new();
+
+    for (i, command) in code.iter().enumerate() {
+        match command {
+            Command::JumpForward => open_brackets.push(i),
+            Command::JumpBackward => {
+                let open = open_brackets
+                    .pop()
+                    .ok_or_else(|| eyre::eyre!("unmatched ']' at instruction {}", i))?;
+                jump_table[open] = i;
+                jump_table[i] = open;
+            }
+            _ => {}
+        }
+    }
+
+    if let Some(open) = open_brackets.pop() {
+        return Err(eyre::eyre!("unmatched '[' at instruction {}", open));
+    }
+
+    Ok(jump_table)
+}
+
+/// Parse raw bytes into commands, dropping anything that isn't a
+/// recognized brainfuck instruction.
+fn parse_commands(bytes: &[u8]) -> Vec<Command> {
+    bytes
+        .iter()
+        .filter_map(|b| match Command::from(*b) {
+            Command::Unknown => None,
+            command => Some(command),
+        })
+        .collect()
+}
+
+/// Run an interactive REPL: each line is parsed and appended to a
+/// persistent program, which is then run up to the new end of code, so
+/// state (the data pointer and memory) carries over between lines.
+fn run_repl() -> Result<()> {
+    println!("brainfuck REPL. Type `quit` to exit, `reset` to clear state.");
+
+    let mut program = Program::new(
+        Vec::new(),
+        DEFAULT_PROGRAM_MEMORY_SIZE,
+        EofMode::default(),
+        default_features(),
+    )?;
+    let mut line = String::new();
+
+    loop {
+        print!("> ");
+        io::stdout().flush()?;
+
+        line.clear();
+        if io::stdin().read_line(&mut line)? == 0 {
+            // EOF (e.g. input piped in) ends the REPL.
+            break;
+        }
+        let line = line.trim();
+
+        match line {
+            "quit" => break,
+            "reset" => {
+                program.reset();
+                continue;
+            }
+            _ => {}
+        }
+
+        if let Err(e) = program.extend_code(parse_commands(line.as_bytes())) {
+            println!("Error: {:?}", e);
+            continue;
+        }
+        if let Err(e) = program.run() {
+            println!("Error: {:?}", e);
+            continue;
+        }
+
+        println!(
+            "data_ptr: {}, value: {}",
+            program.state.data_ptr,
+            program.state.get_value()
+        );
+    }
+
     Ok(())
 }
 
+/// Run a debugger over `program`: a command loop that lets the user set
+/// breakpoints and watchpoints, single-step, continue, and inspect memory
+/// instead of running the program to completion blind.
+fn run_debugger(mut program: Program) -> Result<()> {
+    println!("Debugger started ({} instructions loaded).", program.code.len());
+    println!("Commands: break <ip>, watch <cell>, step, continue, print <cell>, quit");
+
+    let mut line = String::new();
+    loop {
+        print!("(debug) ");
+        io::stdout().flush()?;
+
+        line.clear();
+        if io::stdin().read_line(&mut line)? == 0 {
+            break;
+        }
+        let mut parts = line.split_whitespace();
+
+        match parts.next() {
+            Some("break") => match parts.next().and_then(|s| s.parse::<usize>().ok()) {
+                Some(ip) => {
+                    program.add_breakpoint(ip);
+                    println!("Breakpoint set at instruction {}", ip);
+                }
+                None => println!("Usage: break <ip>"),
+            },
+            Some("watch") => match parts.next().and_then(|s| s.parse::<usize>().ok()) {
+                Some(cell) => {
+                    program.add_watchpoint(cell);
+                    println!("Watching cell {}", cell);
+                }
+                None => println!("Usage: watch <cell>"),
+            },
+            Some("step") => report_status(program.step_one(), program.step_count),
+            Some("continue") => report_status(program.run_until(), program.step_count),
+            Some("print") => match parts.next().and_then(|s| s.parse::<usize>().ok()) {
+                Some(cell) => {
+                    let value = program.state.memory.get(cell).copied().unwrap_or(0);
+                    println!("cell {}: {}", cell, value);
+                }
+                None => println!("Usage: print <cell>"),
+            },
+            Some("quit") => break,
+            Some(other) => println!("Unknown command: {}", other),
+            None => {}
+        }
+    }
+
+    Ok(())
+}
+
+/// Print the outcome of a debugger step/continue command.
+fn report_status(status: Result<ExecutionStatus>, step_count: usize) {
+    match status {
+        Ok(status) => println!("{:?} (step {})", status, step_count),
+        Err(e) => println!("Error: {:?}", e),
+    }
+}
+
+/// Parse the value of an `--eof=` flag into an [`EofMode`].
+fn parse_eof_mode(value: &str) -> Option<EofMode> {
+    match value {
+        "zero" => Some(EofMode::Zero),
+        "negative-one" => Some(EofMode::NegativeOne),
+        "unchanged" => Some(EofMode::Unchanged),
+        _ => None,
+    }
+}
+
 /* ========== MAIN ========== */
 
 fn main() {
     // Read code
     let args: Vec<String> = std::env::args().collect();
     if args.len() < 2 {
-        println!("Please provide the name of the program file.");
+        if let Err(e) = run_repl() {
+            println!("\nREPL exited with error: {:?}", e);
+        }
         return;
     }
 
+    // Parse the remaining flags: `--debug` to drop into the debugger,
+    // `--eof=<zero|negative-one|unchanged>` to pick the EOF policy, and
+    // `--feature=<wrapping-pointer|no-reverse-counter>` to toggle
+    // wraparound dialects.
+    let mut debug = false;
+    let mut eof_mode = EofMode::default();
+    let mut features = default_features();
+    for arg in &args[2..] {
+        if arg == "--debug" {
+            debug = true;
+        } else if let Some(mode) = arg.strip_prefix("--eof=").and_then(parse_eof_mode) {
+            eof_mode = mode;
+        } else if arg == "--feature=wrapping-pointer" {
+            features.push(Feature::WrappingPointer);
+        } else if arg == "--feature=no-reverse-counter" {
+            features.retain(|f| *f != Feature::ReverseCounter);
+        } else {
+            println!("Unknown argument: {}", arg);
+            return;
+        }
+    }
+
     let filename = &args[1];
-    let code: Vec<_> = std::fs::read(filename)
-        .expect("Failed to read from program file")
-        .iter()
-        .filter_map(|b| match Command::from(*b) {
-            Command::Unknown => None,
-            command => Some(command),
-        })
-        .collect();
+    let code = parse_commands(
+        &std::fs::read(filename).expect("Failed to read from program file"),
+    );
 
     // println!("Code: {:?}", code);
 
-    // TODO: Validate code
+    // Parse and validate code, then execute it.
+    let mut program = match Program::new(code, DEFAULT_PROGRAM_MEMORY_SIZE, eof_mode, features) {
+        Ok(program) => program,
+        Err(e) => {
+            println!("\nFailed to load program: {:?}", e);
+            return;
+        }
+    };
+
+    if debug {
+        if let Err(e) = run_debugger(program) {
+            println!("\nDebugger exited with error: {:?}", e);
+        }
+        return;
+    }
 
-    // Execute code
-    let mut program = Program::new();
-    let result = program.run(&code);
+    let result = program.run();
 
     if let Err(e) = result {
         println!("\nProgram exited with error: {:?}", e);
     }
 }
+
+/* ========== Tests ========== */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn build_jump_table_balanced() {
+        // "[+[-]]" -> indices 0 and 5 match, 2 and 4 match.
+        let code = parse_commands(b"[+[-]]");
+        let jump_table = build_jump_table(&code).expect("balanced brackets should validate");
+        assert_eq!(jump_table[0], 5);
+        assert_eq!(jump_table[5], 0);
+        assert_eq!(jump_table[2], 4);
+        assert_eq!(jump_table[4], 2);
+    }
+
+    #[test]
+    fn build_jump_table_unmatched_open() {
+        let code = parse_commands(b"[+");
+        let err = build_jump_table(&code).expect_err("unmatched '[' should be rejected");
+        assert!(err.to_string().contains("unmatched '['"));
+    }
+
+    #[test]
+    fn build_jump_table_unmatched_close() {
+        let code = parse_commands(b"+]");
+        let err = build_jump_table(&code).expect_err("unmatched ']' should be rejected");
+        assert!(err.to_string().contains("unmatched ']'"));
+    }
+
+    #[test]
+    fn breakpoint_at_entry_instruction_halts_before_executing() {
+        let code = parse_commands(b"+");
+        let mut program =
+            Program::new(code, DEFAULT_PROGRAM_MEMORY_SIZE, EofMode::default(), Vec::new())
+                .unwrap();
+        program.add_breakpoint(0);
+
+        let status = program.step_one().unwrap();
+        assert_eq!(status, ExecutionStatus::HitBreakpoint);
+        assert_eq!(program.state.instruct_ptr, 0);
+        assert_eq!(program.state.memory[0], 0, "breakpoint must fire before '+' runs");
+    }
+
+    #[test]
+    fn breakpoint_does_not_immediately_refire_after_resuming() {
+        let code = parse_commands(b"+");
+        let mut program =
+            Program::new(code, DEFAULT_PROGRAM_MEMORY_SIZE, EofMode::default(), Vec::new())
+                .unwrap();
+        program.add_breakpoint(0);
+
+        assert_eq!(program.step_one().unwrap(), ExecutionStatus::HitBreakpoint);
+        // Resuming should execute the instruction instead of halting again.
+        assert_eq!(program.step_one().unwrap(), ExecutionStatus::Running);
+        assert_eq!(program.state.memory[0], 1);
+        assert_eq!(program.step_one().unwrap(), ExecutionStatus::EndOfProgram);
+    }
+
+    #[test]
+    fn increment_pointer_grows_tape_past_initial_size() {
+        let code = parse_commands(b">");
+        let mut program = Program::new(code, 1, EofMode::default(), default_features()).unwrap();
+
+        assert_eq!(program.step_one().unwrap(), ExecutionStatus::Running);
+        assert_eq!(program.state.data_ptr, 1);
+        assert_eq!(program.state.memory.len(), 2);
+        assert_eq!(program.step_one().unwrap(), ExecutionStatus::EndOfProgram);
+    }
+
+    #[test]
+    fn decrement_pointer_at_start_of_tape_errors_without_wrapping_feature() {
+        let code = parse_commands(b"<");
+        let mut program = Program::new(code, 1, EofMode::default(), default_features()).unwrap();
+
+        let err = program
+            .step_one()
+            .expect_err("decrementing past tape start should error, not panic");
+        assert!(err.to_string().contains("below the start of the tape"));
+    }
+
+    #[test]
+    fn value_arithmetic_wraps_with_reverse_counter_feature() {
+        let code = parse_commands(b"-+");
+        let mut program = Program::new(
+            code,
+            DEFAULT_PROGRAM_MEMORY_SIZE,
+            EofMode::default(),
+            default_features(),
+        )
+        .unwrap();
+
+        assert_eq!(program.step_one().unwrap(), ExecutionStatus::Running);
+        assert_eq!(program.state.memory[0], 255, "'-' on 0 should wrap to 255");
+        assert_eq!(program.step_one().unwrap(), ExecutionStatus::Running);
+        assert_eq!(program.state.memory[0], 0, "'+' on 255 should wrap to 0");
+        assert_eq!(program.step_one().unwrap(), ExecutionStatus::EndOfProgram);
+    }
+
+    #[test]
+    fn wrapping_pointer_feature_wraps_at_high_end_of_tape() {
+        let code = parse_commands(b">>>");
+        let mut program =
+            Program::new(code, 3, EofMode::default(), vec![Feature::WrappingPointer]).unwrap();
+
+        assert_eq!(program.step_one().unwrap(), ExecutionStatus::Running);
+        assert_eq!(program.state.data_ptr, 1);
+        assert_eq!(program.step_one().unwrap(), ExecutionStatus::Running);
+        assert_eq!(program.state.data_ptr, 2);
+        assert_eq!(program.step_one().unwrap(), ExecutionStatus::Running);
+        assert_eq!(
+            program.state.data_ptr, 0,
+            "pointer should wrap back to the start of the tape"
+        );
+        assert_eq!(
+            program.state.memory.len(),
+            3,
+            "tape should not grow when wrapping is enabled"
+        );
+    }
+
+    #[test]
+    fn wrapping_pointer_feature_wraps_at_low_end_of_tape() {
+        let code = parse_commands(b"<");
+        let mut program =
+            Program::new(code, 3, EofMode::default(), vec![Feature::WrappingPointer]).unwrap();
+
+        assert_eq!(program.step_one().unwrap(), ExecutionStatus::Running);
+        assert_eq!(
+            program.state.data_ptr, 2,
+            "decrementing past the start should wrap to the end of the tape"
+        );
+    }
+
+    #[test]
+    fn no_reverse_counter_feature_saturates_value_arithmetic() {
+        let mut program = Program::new(
+            parse_commands(b"-"),
+            DEFAULT_PROGRAM_MEMORY_SIZE,
+            EofMode::default(),
+            Vec::new(),
+        )
+        .unwrap();
+        assert_eq!(program.step_one().unwrap(), ExecutionStatus::Running);
+        assert_eq!(
+            program.state.memory[0], 0,
+            "'-' on 0 should saturate at 0 rather than wrap to 255"
+        );
+
+        let mut program = Program::new(
+            parse_commands(b"+"),
+            DEFAULT_PROGRAM_MEMORY_SIZE,
+            EofMode::default(),
+            Vec::new(),
+        )
+        .unwrap();
+        program.state.memory[0] = 255;
+        assert_eq!(program.step_one().unwrap(), ExecutionStatus::Running);
+        assert_eq!(
+            program.state.memory[0], 255,
+            "'+' on 255 should saturate at 255 rather than wrap to 0"
+        );
+    }
+
+    // `InputValue` reads from the process's real stdin, which `cargo test`
+    // runs with closed/empty, so these reliably exercise the EOF path of
+    // each `EofMode` rather than a live byte.
+
+    #[test]
+    fn eof_mode_zero_writes_zero_cell_on_eof() {
+        let code = parse_commands(b",");
+        let mut program =
+            Program::new(code, DEFAULT_PROGRAM_MEMORY_SIZE, EofMode::Zero, Vec::new()).unwrap();
+        program.state.memory[0] = 42;
+
+        assert_eq!(program.step_one().unwrap(), ExecutionStatus::Running);
+        assert_eq!(program.state.memory[0], 0);
+    }
+
+    #[test]
+    fn eof_mode_negative_one_writes_255_on_eof() {
+        let code = parse_commands(b",");
+        let mut program = Program::new(
+            code,
+            DEFAULT_PROGRAM_MEMORY_SIZE,
+            EofMode::NegativeOne,
+            Vec::new(),
+        )
+        .unwrap();
+
+        assert_eq!(program.step_one().unwrap(), ExecutionStatus::Running);
+        assert_eq!(program.state.memory[0], 255);
+    }
+
+    #[test]
+    fn eof_mode_unchanged_leaves_cell_value_on_eof() {
+        let code = parse_commands(b",");
+        let mut program = Program::new(
+            code,
+            DEFAULT_PROGRAM_MEMORY_SIZE,
+            EofMode::Unchanged,
+            Vec::new(),
+        )
+        .unwrap();
+        program.state.memory[0] = 42;
+
+        assert_eq!(program.step_one().unwrap(), ExecutionStatus::Running);
+        assert_eq!(program.state.memory[0], 42);
+    }
+}